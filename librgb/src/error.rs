@@ -82,4 +82,14 @@ pub(crate) enum RequestError {
     /// Bitcoin consensus encode error: {_0}
     #[from]
     ConsensusEncode(bitcoin::consensus::encode::Error),
+
+    /// Hardware wallet error: {_0}
+    Hardware(String),
+
+    /// Electrum server error: {_0}
+    #[from]
+    Electrum(electrum_client::Error),
+
+    /// Insufficient asset balance for transfer: {_0}
+    TransferFailed(String),
 }
@@ -11,7 +11,7 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use std::os::raw::{c_char, c_double, c_uchar};
+use std::os::raw::{c_char, c_double, c_uchar, c_ushort};
 
 use crate::helpers::*;
 use crate::internal::*;
@@ -150,6 +150,43 @@ pub extern "C" fn rgb_node_fungible_transfer(
     .into()
 }
 
+#[no_mangle]
+pub extern "C" fn rgb_node_fungible_transfer_hw(
+    runtime: &COpaqueStruct,
+    contract_id: *const c_char,
+    inputs: *const c_char,
+    payment: *const c_char,
+    change: *const c_char,
+    unsigned_psbt: *const c_char,
+) -> CResultString {
+    _transfer_hw(
+        runtime,
+        contract_id,
+        inputs,
+        payment,
+        change,
+        unsigned_psbt,
+    )
+    .into()
+}
+
+#[no_mangle]
+pub extern "C" fn rgb_estimate_feerate(
+    runtime: &COpaqueStruct,
+    target_blocks: c_uchar,
+) -> CResultString {
+    _estimate_feerate(runtime, target_blocks).into()
+}
+
+#[no_mangle]
+pub extern "C" fn rgb_node_fungible_bump_fee(
+    runtime: &COpaqueStruct,
+    witness_psbt: *const c_char,
+    new_feerate: c_double,
+) -> CResultString {
+    _bump_fee(runtime, witness_psbt, new_feerate).into()
+}
+
 #[no_mangle]
 pub extern "C" fn rgb_node_fungible_validate(
     runtime: &COpaqueStruct,
@@ -166,3 +203,43 @@ pub extern "C" fn rgb_node_fungible_accept(
 ) -> CResult {
     _accept(runtime, consignment_file, reveal_outpoints).into()
 }
+
+#[no_mangle]
+pub extern "C" fn rgb_consignment_to_packets(
+    consignment_bech32: *const c_char,
+    symbol_size: c_ushort,
+) -> CResultString {
+    _consignment_to_packets(consignment_bech32, symbol_size).into()
+}
+
+#[no_mangle]
+pub extern "C" fn rgb_consignment_from_packets(
+    packets_json: *const c_char,
+) -> CResultString {
+    _consignment_from_packets(packets_json).into()
+}
+
+#[no_mangle]
+pub extern "C" fn rgb_offer_create(
+    contract_id: *const c_char,
+    amount: *const c_char,
+    ticker: *const c_char,
+    description: *const c_char,
+    expiry: *const c_char,
+) -> CResultString {
+    _offer_create(contract_id, amount, ticker, description, expiry).into()
+}
+
+#[no_mangle]
+pub extern "C" fn rgb_offer_parse(offer_str: *const c_char) -> CResultString {
+    _offer_parse(offer_str).into()
+}
+
+#[no_mangle]
+pub extern "C" fn rgb_invoice_from_offer(
+    offer_str: *const c_char,
+    available_outpoints: *const c_char,
+    payer_amount: c_double,
+) -> CResultString {
+    _invoice_from_offer(offer_str, available_outpoints, payer_amount).into()
+}
@@ -14,11 +14,18 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 use std::collections::{HashMap, BTreeSet, BTreeMap};
+use std::convert::TryInto;
 use std::ffi::CStr;
-use std::os::raw::{c_char, c_double, c_uchar};
+use std::os::raw::{c_char, c_double, c_uchar, c_ushort};
 use std::str::FromStr;
 use std::iter::FromIterator;
 
+use raptorq::{Decoder, Encoder, EncodingPacket, ObjectTransmissionInformation};
+use base64;
+use ledger_apdu::APDUCommand;
+use ledger_transport_hid::{hidapi::HidApi, TransportNativeHID};
+use electrum_client::ElectrumApi;
+
 #[cfg(not(target_os = "android"))]
 use log::LevelFilter;
 #[cfg(not(target_os = "android"))]
@@ -32,15 +39,17 @@ use bitcoin::consensus::{Decodable, Encodable};
 use lnpbp::Chain;
 use lnpbp::seals::{OutpointReveal, OutpointHash};
 use rgb::{
-    Consignment, ContractId, FromBech32, Genesis, //SealDefinition,
+    Consignment, ContractId, FromBech32, Genesis, SealDefinition,
     SealEndpoint, AtomicValue,
 };
 
 use rgb_node::rpc::reply::SyncFormat;
 use rgb20::{Asset, Invoice, Outpoint, OutpointCoins, SealCoins};
 use rgb_node::i9n::{Config, Runtime};
-use lnpbp::strict_encoding::strict_deserialize;
+use lnpbp::strict_encoding::{strict_deserialize, strict_serialize, StrictDecode, StrictEncode};
 use lnpbp::bech32::ToBech32String;
+use serde::{Deserialize, Serialize};
+use bech32::{FromBase32, ToBase32};
 use rgb_node::rgbd::ContractName;
 use rgb_node::util::file::ReadWrite;
 use microservices::FileFormat;
@@ -323,18 +332,21 @@ pub(crate) fn _invoice(
     Ok(json_response.to_string())
 }
 
-pub(crate) fn _transfer(
-    runtime: &COpaqueStruct,
-    contract_id: *const c_char,
+/// Shared inputs/payment/change parsing for [`_transfer`] and
+/// [`_transfer_hw`], so the two entry points can't drift on argument
+/// format.
+fn _parse_transfer_args(
     inputs: *const c_char,
     payment: *const c_char,
     change: *const c_char,
-    witness: *const c_char,
-) -> Result<String, RequestError> {
-    let runtime = Runtime::from_opaque(runtime)?;
-
-    let contract_id = ContractId::from_str(&ptr_to_string(contract_id)?)?;
-
+) -> Result<
+    (
+        BTreeSet<OutPoint>,
+        BTreeMap<SealEndpoint, AtomicValue>,
+        BTreeMap<SealDefinition, AtomicValue>,
+    ),
+    RequestError,
+> {
     let v_inputs: Vec<OutPoint> = serde_json::from_str(&ptr_to_string(inputs)?)?;
     let inputs: BTreeSet<OutPoint> = BTreeSet::from_iter(v_inputs.into_iter());
 
@@ -363,6 +375,23 @@ pub(crate) fn _transfer(
         change.insert(seal_coins.seal_definition(), seal_coins.coins);
     }
 
+    Ok((inputs, payment, change))
+}
+
+pub(crate) fn _transfer(
+    runtime: &COpaqueStruct,
+    contract_id: *const c_char,
+    inputs: *const c_char,
+    payment: *const c_char,
+    change: *const c_char,
+    witness: *const c_char,
+) -> Result<String, RequestError> {
+    let runtime = Runtime::from_opaque(runtime)?;
+
+    let contract_id = ContractId::from_str(&ptr_to_string(contract_id)?)?;
+
+    let (inputs, payment, change) = _parse_transfer_args(inputs, payment, change)?;
+
     let c_witness = unsafe { CStr::from_ptr(witness) };
     let mut data = c_witness.to_bytes();
     let witness = PartiallySignedTransaction::consensus_decode(&mut data)?;
@@ -372,6 +401,35 @@ pub(crate) fn _transfer(
         contract_id, inputs, payment, change, witness,
     );
 
+    let requested_total: AtomicValue =
+        payment.values().sum::<AtomicValue>() + change.values().sum::<AtomicValue>();
+
+    let allocations = runtime.asset_allocations(contract_id)?;
+    let mut available_by_outpoint: BTreeMap<OutPoint, AtomicValue> = bmap!{};
+    for input in &inputs {
+        let available: AtomicValue = allocations
+            .iter()
+            .filter(|allocation| &allocation.outpoint == input)
+            .map(|allocation| allocation.coins)
+            .sum();
+        available_by_outpoint.insert(*input, available);
+    }
+    let available_total: AtomicValue = available_by_outpoint.values().sum();
+
+    if available_total < requested_total {
+        let detail = json!({
+            "contract_id": contract_id.to_string(),
+            "requested_total": requested_total,
+            "available_total": available_total,
+            "available_by_outpoint": available_by_outpoint
+                .iter()
+                .map(|(outpoint, coins)| (outpoint.to_string(), coins))
+                .collect::<BTreeMap<_, _>>(),
+            "shortfall": requested_total - available_total,
+        });
+        return Err(RequestError::TransferFailed(detail.to_string()));
+    }
+
     let transfer = runtime.transfer(
         contract_id,
         inputs,
@@ -391,6 +449,322 @@ pub(crate) fn _transfer(
     Ok(json_transfer.to_string())
 }
 
+/// CLA/INS pair for the Bitcoin app's "sign PSBT" APDU command, chunked
+/// across multiple exchanges since a PSBT rarely fits in one APDU payload.
+const LEDGER_CLA: u8 = 0xe0;
+const LEDGER_INS_SIGN_PSBT: u8 = 0x02;
+const LEDGER_APDU_CHUNK_SIZE: usize = 255;
+
+/// Streams `psbt` to a connected Ledger device over APDU and merges the
+/// returned partial signatures back in, leaving unrelated PSBT fields
+/// untouched.
+fn _ledger_sign_psbt(psbt: &mut PartiallySignedTransaction) -> Result<(), RequestError> {
+    let hidapi = HidApi::new().map_err(|e| RequestError::Hardware(e.to_string()))?;
+    let transport = TransportNativeHID::new(&hidapi)
+        .map_err(|e| RequestError::Hardware(e.to_string()))?;
+
+    let mut psbt_bytes = vec![];
+    psbt.consensus_encode(&mut psbt_bytes)?;
+
+    let mut signed_bytes = vec![];
+    let chunks: Vec<&[u8]> = psbt_bytes.chunks(LEDGER_APDU_CHUNK_SIZE).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let command = APDUCommand {
+            cla: LEDGER_CLA,
+            ins: LEDGER_INS_SIGN_PSBT,
+            p1: if i == 0 { 0x00 } else { 0x80 },
+            p2: if i + 1 == chunks.len() { 0x00 } else { 0x80 },
+            data: chunk.to_vec(),
+        };
+
+        let answer = transport
+            .exchange(&command)
+            .map_err(|e| RequestError::Hardware(format!("APDU transport error: {}", e)))?;
+
+        if answer.retcode() != 0x9000 {
+            return Err(RequestError::Hardware(format!(
+                "Ledger device rejected the signing request (status {:04x})",
+                answer.retcode()
+            )));
+        }
+        signed_bytes.extend_from_slice(answer.data());
+    }
+
+    let signed_psbt = PartiallySignedTransaction::consensus_decode(&mut signed_bytes.as_slice())?;
+    if signed_psbt.inputs.len() != psbt.inputs.len() {
+        return Err(RequestError::Hardware(format!(
+            "Ledger returned a signed PSBT with {} inputs, expected {}",
+            signed_psbt.inputs.len(),
+            psbt.inputs.len()
+        )));
+    }
+    for (input, signed_input) in psbt.inputs.iter_mut().zip(signed_psbt.inputs.into_iter()) {
+        input.partial_sigs.extend(signed_input.partial_sigs);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn _transfer_hw(
+    runtime: &COpaqueStruct,
+    contract_id: *const c_char,
+    inputs: *const c_char,
+    payment: *const c_char,
+    change: *const c_char,
+    unsigned_psbt: *const c_char,
+) -> Result<String, RequestError> {
+    let runtime = Runtime::from_opaque(runtime)?;
+
+    let contract_id = ContractId::from_str(&ptr_to_string(contract_id)?)?;
+
+    let (inputs, payment, change) = _parse_transfer_args(inputs, payment, change)?;
+
+    let c_witness = unsafe { CStr::from_ptr(unsigned_psbt) };
+    let mut data = c_witness.to_bytes();
+    let mut witness = PartiallySignedTransaction::consensus_decode(&mut data)?;
+
+    debug!(
+        "TransferHwArgs {{contract_id: {}, inputs: {:?}, payment: {:?}, change: {:?}}}",
+        contract_id, inputs, payment, change,
+    );
+
+    _ledger_sign_psbt(&mut witness)?;
+
+    let transfer = runtime.transfer(
+        contract_id,
+        inputs,
+        payment,
+        change,
+        witness,
+    )?;
+
+    let mut data = vec![];
+    transfer.witness.consensus_encode(&mut data)?;
+
+    let json_transfer = json!({
+        "consignment": transfer.consignment.to_bech32_string(),
+        "witness": String::from_utf8(data)
+            .map_err(|e| e.utf8_error())?
+    });
+    Ok(json_transfer.to_string())
+}
+
+/// electrum-client reports feerates in BTC/kB; the RGB node, like the rest
+/// of this API, speaks sat/vB.
+const BTC_PER_KB_TO_SAT_PER_VB: f64 = 100_000f64;
+
+pub(crate) fn _estimate_feerate(
+    runtime: &COpaqueStruct,
+    target_blocks: c_uchar,
+) -> Result<String, RequestError> {
+    let runtime = Runtime::from_opaque(runtime)?;
+
+    let client = electrum_client::Client::new(&runtime.config().electrum_server)?;
+
+    let raw_feerate = client.estimate_fee(target_blocks as usize)?;
+    if raw_feerate < 0f64 {
+        return Err(RequestError::Input(format!(
+            "Electrum server has insufficient data to estimate a feerate for {} blocks",
+            target_blocks
+        )));
+    }
+    let feerate = raw_feerate * BTC_PER_KB_TO_SAT_PER_VB;
+    let min_mempool_feerate = client.relay_fee()? * BTC_PER_KB_TO_SAT_PER_VB;
+
+    debug!(
+        "Estimated feerate for {} blocks: {} sat/vB (mempool floor {} sat/vB)",
+        target_blocks, feerate, min_mempool_feerate
+    );
+
+    let json_response = json!({
+        "feerate": feerate,
+        "min_mempool_feerate": min_mempool_feerate,
+    });
+    Ok(json_response.to_string())
+}
+
+/// Computes the fee (in sats) and vsize (in vbytes) an already-built PSBT
+/// pays, from its input UTXOs (witness or non-witness) and declared outputs.
+fn _psbt_fee_and_vsize(psbt: &PartiallySignedTransaction) -> Result<(u64, f64), RequestError> {
+    let tx = &psbt.global.unsigned_tx;
+
+    let mut input_total = 0u64;
+    for (i, input) in psbt.inputs.iter().enumerate() {
+        let value = if let Some(utxo) = &input.witness_utxo {
+            utxo.value
+        } else if let Some(prev_tx) = &input.non_witness_utxo {
+            let vout = tx.input[i].previous_output.vout as usize;
+            if vout >= prev_tx.output.len() {
+                return Err(RequestError::Input(format!(
+                    "PSBT input {} references output {} but its non-witness UTXO only has {} outputs",
+                    i, vout, prev_tx.output.len()
+                )));
+            }
+            prev_tx.output[vout].value
+        } else {
+            return Err(RequestError::Input(s!(
+                "PSBT input is missing UTXO information needed to compute its feerate"
+            )));
+        };
+        input_total += value;
+    }
+
+    let output_total: u64 = tx.output.iter().map(|output| output.value).sum();
+    let fee = input_total.saturating_sub(output_total);
+    let vsize = tx.get_weight() as f64 / 4.0;
+
+    Ok((fee, vsize))
+}
+
+/// Below this value a change output isn't worth keeping; CPFP is used
+/// instead of shrinking it in place.
+const DUST_LIMIT: u64 = 546;
+
+/// Sequence number signaling BIP125 opt-in replaceability.
+const BIP125_SEQUENCE: u32 = 0xffff_fffd;
+
+/// This RGB node's `Runtime` exposes only the single, monolithic `transfer`
+/// call used by [`_transfer`] — there is no stash-level API for rewriting
+/// an already-emitted witness transaction. A fee bump therefore stays
+/// entirely at the Bitcoin layer: it does not touch any RGB seal or
+/// consignment, since neither the inputs nor the seal-bearing outputs
+/// change, only the change output (RBF) or an added child spending it
+/// (CPFP). The bumped PSBT is returned unsigned. With `"method": "rbf"` it is
+/// the replacement witness transaction, which can be re-signed and
+/// (re)submitted via `rgb_node_fungible_transfer`/`_transfer_hw`; with
+/// `"method": "cpfp"` it is a separate child transaction that spends only the
+/// witness's change output and is not itself an RGB witness.
+pub(crate) fn _bump_fee(
+    runtime: &COpaqueStruct,
+    witness_psbt: *const c_char,
+    new_feerate: c_double,
+) -> Result<String, RequestError> {
+    let runtime = Runtime::from_opaque(runtime)?;
+
+    let c_witness = unsafe { CStr::from_ptr(witness_psbt) };
+    let mut data = c_witness.to_bytes();
+    let psbt = PartiallySignedTransaction::consensus_decode(&mut data)?;
+
+    let client = electrum_client::Client::new(&runtime.config().electrum_server)?;
+    let min_mempool_feerate = client.relay_fee()? * BTC_PER_KB_TO_SAT_PER_VB;
+
+    if new_feerate < min_mempool_feerate {
+        return Err(RequestError::Input(format!(
+            "Requested feerate {} sat/vB is below the mempool minimum relay feerate {} sat/vB; \
+            the replacement would not relay",
+            new_feerate, min_mempool_feerate
+        )));
+    }
+
+    let (fee, vsize) = _psbt_fee_and_vsize(&psbt)?;
+    let original_feerate = fee as f64 / vsize;
+    if new_feerate <= original_feerate {
+        return Err(RequestError::Input(format!(
+            "Requested feerate {} sat/vB does not exceed the original witness's feerate {} sat/vB; \
+            a replacement must pay strictly more to satisfy BIP125",
+            new_feerate, original_feerate
+        )));
+    }
+
+    if psbt.global.unsigned_tx.output.is_empty() {
+        return Err(RequestError::Input(s!(
+            "Witness transaction has no outputs to treat as change for a fee bump"
+        )));
+    }
+
+    let target_fee = (new_feerate * vsize).ceil() as u64;
+    let fee_delta = target_fee.saturating_sub(fee);
+
+    let mut tx = psbt.global.unsigned_tx.clone();
+    let change_index = tx.output.len() - 1;
+
+    let (method, bumped_tx) = if tx.output[change_index].value > fee_delta + DUST_LIMIT {
+        // RBF: absorb the extra fee by shrinking the change output in place.
+        tx.output[change_index].value -= fee_delta;
+        for input in tx.input.iter_mut() {
+            input.sequence = BIP125_SEQUENCE;
+        }
+        ("rbf", tx)
+    } else {
+        // CPFP: the change output can't absorb the bump without going
+        // below dust, so spend it as the sole input of a child transaction
+        // that pays enough fee for the *combined* parent+child package to
+        // clear `new_feerate`, while its own standalone feerate still
+        // clears the mempool floor so it relays on its own too.
+        //
+        // This requires the parent's txid to be stable once signed, which
+        // only holds if every input is segwit (`witness_utxo`): a legacy
+        // (`non_witness_utxo`) input's scriptSig is part of its txid, so
+        // signing it would change the very outpoint this child spends.
+        let parent_is_txid_stable = psbt
+            .inputs
+            .iter()
+            .all(|input| input.witness_utxo.is_some());
+        if !parent_is_txid_stable {
+            return Err(RequestError::Input(s!(
+                "Change output is too small to absorb an in-place (RBF) fee bump, and CPFP is \
+                unsafe here because a legacy (non-witness) input's txid changes once signed"
+            )));
+        }
+
+        let parent_change = tx.output[change_index].clone();
+
+        // Measure the child's own vsize from its fixed shape (one input
+        // spending the change output, one output returning to the same
+        // script) before solving for its fee.
+        let child_shape = bitcoin::Transaction {
+            version: tx.version,
+            lock_time: 0,
+            input: vec![bitcoin::TxIn {
+                previous_output: OutPoint::new(tx.txid(), change_index as u32),
+                script_sig: bitcoin::Script::new(),
+                sequence: BIP125_SEQUENCE,
+                witness: vec![],
+            }],
+            output: vec![bitcoin::TxOut {
+                value: parent_change.value,
+                script_pubkey: parent_change.script_pubkey.clone(),
+            }],
+        };
+        let child_vsize = child_shape.get_weight() as f64 / 4.0;
+
+        let package_fee_needed = (new_feerate * (vsize + child_vsize)).ceil() as u64;
+        let child_fee_for_package = package_fee_needed.saturating_sub(fee);
+        let child_fee_for_floor = (min_mempool_feerate * child_vsize).ceil() as u64;
+        let child_fee = child_fee_for_package.max(child_fee_for_floor);
+
+        if child_fee + DUST_LIMIT > parent_change.value {
+            return Err(RequestError::Input(format!(
+                "Change output of {} sats is too small to pay a CPFP child fee of {} sats and \
+                stay above the {} sat dust limit",
+                parent_change.value, child_fee, DUST_LIMIT
+            )));
+        }
+
+        let mut child_tx = child_shape;
+        child_tx.output[0].value = parent_change.value - child_fee;
+        ("cpfp", child_tx)
+    };
+
+    let bumped_psbt = PartiallySignedTransaction::from_unsigned_tx(bumped_tx)
+        .map_err(|e| RequestError::Input(format!("Failed to build fee-bump PSBT: {}", e)))?;
+
+    debug!(
+        "Fee-bumped witness via {} from {} to {} sat/vB (mempool floor {} sat/vB)",
+        method, original_feerate, new_feerate, min_mempool_feerate
+    );
+
+    let mut data = vec![];
+    bumped_psbt.consensus_encode(&mut data)?;
+
+    let json_response = json!({
+        "method": method,
+        "witness": String::from_utf8(data)
+            .map_err(|e| e.utf8_error())?
+    });
+    Ok(json_response.to_string())
+}
+
 pub(crate) fn _validate(
     runtime: &COpaqueStruct,
     consignment_file: *const c_char,
@@ -432,3 +806,263 @@ pub(crate) fn _accept(
 
     Ok(())
 }
+
+/// Number of repair symbols emitted in addition to the `k` source symbols,
+/// so a scanner can recover from a small amount of packet loss without
+/// re-running the whole animated QR sequence.
+const RAPTORQ_REPAIR_SYMBOLS: u32 = 8;
+
+/// Size in bytes of a serialized `ObjectTransmissionInformation` header.
+const RAPTORQ_OTI_LEN: usize = 12;
+
+/// Minimum size in bytes of a serialized `EncodingPacket`: a 4-byte payload
+/// id (source block number + encoding symbol id) plus at least one byte of
+/// symbol data.
+const RAPTORQ_MIN_PACKET_LEN: usize = 5;
+
+pub(crate) fn _consignment_to_packets(
+    consignment_bech32: *const c_char,
+    symbol_size: c_ushort,
+) -> Result<String, RequestError> {
+    let consignment = Consignment::from_bech32_str(&ptr_to_string(consignment_bech32)?)?;
+
+    let data = strict_serialize(&consignment)?;
+
+    debug!(
+        "Splitting consignment ({} bytes) into RaptorQ packets with symbol size {}",
+        data.len(),
+        symbol_size
+    );
+
+    let encoder = Encoder::with_defaults(&data, symbol_size);
+    let oti = encoder.get_config().serialize();
+
+    let packets: Vec<String> = encoder
+        .get_encoded_packets(RAPTORQ_REPAIR_SYMBOLS)
+        .into_iter()
+        .map(|packet| {
+            let mut buf = oti.to_vec();
+            buf.extend(packet.serialize());
+            base64::encode(&buf)
+        })
+        .collect();
+
+    Ok(serde_json::to_string(&packets)?)
+}
+
+pub(crate) fn _consignment_from_packets(
+    packets_json: *const c_char,
+) -> Result<String, RequestError> {
+    let packets: Vec<String> = serde_json::from_str(&ptr_to_string(packets_json)?)?;
+
+    let mut decoder: Option<Decoder> = None;
+    let mut reconstructed: Option<Vec<u8>> = None;
+
+    for packet_b64 in packets {
+        let buf = base64::decode(&packet_b64)
+            .map_err(|e| RequestError::Input(format!("Invalid packet base64: {}", e)))?;
+
+        if buf.len() < RAPTORQ_OTI_LEN {
+            return Err(RequestError::Input(s!(
+                "Packet too short to contain an ObjectTransmissionInformation header"
+            )));
+        }
+        let (oti_bytes, packet_bytes) = buf.split_at(RAPTORQ_OTI_LEN);
+
+        if packet_bytes.len() < RAPTORQ_MIN_PACKET_LEN {
+            return Err(RequestError::Input(s!(
+                "Packet too short to contain a RaptorQ encoding symbol"
+            )));
+        }
+
+        let decoder = decoder.get_or_insert_with(|| {
+            let oti = ObjectTransmissionInformation::deserialize(
+                &oti_bytes.try_into().expect("length checked above"),
+            );
+            Decoder::new(oti)
+        });
+
+        if let Some(data) = decoder.decode(EncodingPacket::deserialize(packet_bytes)) {
+            reconstructed = Some(data);
+            break;
+        }
+    }
+
+    let data = reconstructed.ok_or_else(|| {
+        RequestError::Input(s!(
+            "Not enough distinct packets received to reconstruct the consignment"
+        ))
+    })?;
+
+    let consignment: Consignment = strict_deserialize(&data)?;
+    Ok(consignment.to_bech32_string())
+}
+
+/// Human-readable part for bech32-encoded [`Offer`]s, analogous to the
+/// `lnbc`/`lnurl`-style prefixes used elsewhere in the RGB ecosystem.
+const OFFER_HRP: &str = "offer";
+
+/// A reusable, UTXO-free "pay me in this asset" offer, akin to a BOLT12
+/// offer. Unlike an [`Invoice`], it does not commit to a specific outpoint,
+/// so the same offer can be published as a static QR code and turned into a
+/// fresh blinded-UTXO invoice on every payment via [`_invoice_from_offer`].
+#[derive(Clone, Debug, Serialize, Deserialize, StrictEncode, StrictDecode)]
+pub(crate) struct Offer {
+    pub contract_id: ContractId,
+    /// `None` for amount-optional offers, where the payer picks the amount
+    /// when deriving the invoice.
+    pub amount: Option<AtomicValue>,
+    pub ticker: String,
+    pub description: Option<String>,
+    /// Unix timestamp after which the offer should no longer be honored.
+    pub expiry: Option<i64>,
+}
+
+// `Offer` implements the same `ToBech32String`/`FromBech32` trait interface
+// as `ContractId`, `Consignment` and `Genesis` so callers can treat it
+// uniformly, but the encoding itself is local to this crate: it strict-encodes
+// the struct and bech32m-wraps it under its own `OFFER_HRP`, rather than
+// sharing any HRP registry or versioning/CRC scheme defined inside `rgb`/`lnpbp`.
+impl ToBech32String for Offer {
+    fn to_bech32_string(&self) -> String {
+        let data = strict_serialize(self).expect("in-memory strict encoding of Offer cannot fail");
+        bech32::encode(OFFER_HRP, data.to_base32(), bech32::Variant::Bech32m)
+            .expect("strict-encoded Offer payload is always valid bech32 data")
+    }
+}
+
+impl FromBech32 for Offer {
+    type Err = RequestError;
+
+    fn from_bech32_str(s: &str) -> Result<Offer, RequestError> {
+        let (hrp, data, _variant) = bech32::decode(s)
+            .map_err(|e| RequestError::Input(format!("Invalid offer bech32 string: {}", e)))?;
+        if hrp != OFFER_HRP {
+            return Err(RequestError::Input(format!(
+                "Invalid offer human-readable part: expected '{}', got '{}'",
+                OFFER_HRP, hrp
+            )));
+        }
+        let data = Vec::<u8>::from_base32(&data)
+            .map_err(|e| RequestError::Input(format!("Invalid offer bech32 payload: {}", e)))?;
+        Ok(strict_deserialize(&data)?)
+    }
+}
+
+pub(crate) fn _offer_create(
+    contract_id: *const c_char,
+    amount: *const c_char,
+    ticker: *const c_char,
+    description: *const c_char,
+    expiry: *const c_char,
+) -> Result<String, RequestError> {
+    let contract_id = ContractId::from_str(&ptr_to_string(contract_id)?)?;
+
+    let amount = if amount.is_null() {
+        None
+    } else {
+        let amount = ptr_to_string(amount)?;
+        if amount.is_empty() {
+            None
+        } else {
+            Some(amount.parse::<AtomicValue>()?)
+        }
+    };
+
+    let ticker = ptr_to_string(ticker)?;
+
+    let description = if description.is_null() {
+        None
+    } else {
+        let description = ptr_to_string(description)?;
+        if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        }
+    };
+
+    let expiry = if expiry.is_null() {
+        None
+    } else {
+        let expiry = ptr_to_string(expiry)?;
+        if expiry.is_empty() {
+            None
+        } else {
+            Some(expiry.parse::<i64>()?)
+        }
+    };
+
+    let offer = Offer {
+        contract_id,
+        amount,
+        ticker,
+        description,
+        expiry,
+    };
+
+    debug!("Created offer {:?}", offer);
+
+    Ok(offer.to_bech32_string())
+}
+
+pub(crate) fn _offer_parse(offer_str: *const c_char) -> Result<String, RequestError> {
+    let offer = Offer::from_bech32_str(&ptr_to_string(offer_str)?)?;
+    Ok(serde_json::to_string(&offer)?)
+}
+
+pub(crate) fn _invoice_from_offer(
+    offer_str: *const c_char,
+    available_outpoints: *const c_char,
+    payer_amount: c_double,
+) -> Result<String, RequestError> {
+    let offer = Offer::from_bech32_str(&ptr_to_string(offer_str)?)?;
+
+    if let Some(expiry) = offer.expiry {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(i64::MAX);
+        if now >= expiry {
+            return Err(RequestError::Input(format!(
+                "Offer expired at {} (current time {})",
+                expiry, now
+            )));
+        }
+    }
+
+    let available_outpoints: Vec<OutPoint> =
+        serde_json::from_str(&ptr_to_string(available_outpoints)?)?;
+    let outpoint = available_outpoints
+        .into_iter()
+        .next()
+        .ok_or_else(|| RequestError::Input(s!("No available outpoints supplied")))?;
+
+    let amount = match offer.amount {
+        Some(fixed) => fixed as f64,
+        None if payer_amount > 0.0 => payer_amount,
+        None => {
+            return Err(RequestError::Input(s!(
+                "Offer is amount-optional; a positive amount must be supplied to derive an invoice"
+            )))
+        }
+    };
+
+    let outpoint_reveal = OutpointReveal::from(outpoint);
+    let invoice = Invoice {
+        contract_id: offer.contract_id,
+        outpoint: Outpoint::BlindedUtxo(outpoint_reveal.commit_conceal()),
+        amount,
+    };
+
+    debug!(
+        "Derived invoice {} from offer for contract {}, blinding factor {}",
+        invoice, offer.contract_id, outpoint_reveal.blinding
+    );
+
+    let json_response = json!({
+        "invoice": invoice.to_string(),
+        "secret": outpoint_reveal.blinding
+    });
+    Ok(json_response.to_string())
+}